@@ -18,10 +18,11 @@
 
 use std::net::IpAddr;
 use std::fmt;
+use std::str::FromStr;
 use std::string::String;
 use std::string::ToString;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct IpNetwork {
     addr: IpAddr,
     prefix: u8,
@@ -33,13 +34,206 @@ impl fmt::Display for IpNetwork {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+/// Widens an address into a `u128` along with its bit width (32 for IPv4,
+/// 128 for IPv6), so v4 and v6 masking can share the same arithmetic.
+fn addr_to_bits(addr: &IpAddr) -> (u128, u32) {
+    match *addr {
+        IpAddr::V4(v4) => (u32::from(v4) as u128, 32),
+        IpAddr::V6(v6) => (u128::from(v6), 128),
+    }
+}
+
+/// The `prefix`-bit mask for a `width`-bit address, held in the low `width`
+/// bits of the result, e.g. prefix 24, width 32 gives `0xffffff00` there.
+/// The bits above `width` are set as well (except for prefix 0); addresses
+/// from `addr_to_bits` have those bits clear, so they drop out when masked.
+fn prefix_mask(prefix: u8, width: u32) -> u128 {
+    if prefix == 0 {
+        0
+    } else {
+        !0u128 << (width - prefix as u32)
+    }
+}
+
+impl IpNetwork {
+    /// Masks the host bits off this network's address.
+    pub fn network(&self) -> IpAddr {
+        let (bits, width) = addr_to_bits(&self.addr);
+        let masked = bits & prefix_mask(self.prefix, width);
+        match self.addr {
+            IpAddr::V4(_) => IpAddr::V4(std::net::Ipv4Addr::from(masked as u32)),
+            IpAddr::V6(_) => IpAddr::V6(std::net::Ipv6Addr::from(masked)),
+        }
+    }
+
+    /// Whether `addr` falls within this network.
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        let (self_bits, width) = addr_to_bits(&self.addr);
+        let (other_bits, other_width) = addr_to_bits(addr);
+        if width != other_width {
+            return false;
+        }
+        let mask = prefix_mask(self.prefix, width);
+        self_bits & mask == other_bits & mask
+    }
+
+    /// Whether `self` and `other` share any addresses.
+    pub fn overlaps(&self, other: &IpNetwork) -> bool {
+        self.contains(&other.addr) || other.contains(&self.addr)
+    }
+}
+
+/// Errors produced when parsing an [`IpNetwork`] from a string in either
+/// CIDR (`10.0.0.1/24`) or dotted-netmask (`10.0.0.1/255.255.255.0`) form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IpNetworkParseError {
+    MissingPrefix,
+    InvalidAddress(String),
+    InvalidPrefix(String),
+    NonContiguousNetmask(String),
+    PrefixTooLarge { prefix: u8, max: u8 },
+}
+
+impl fmt::Display for IpNetworkParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IpNetworkParseError::MissingPrefix =>
+                write!(f, "missing prefix or netmask (expected addr/prefix)"),
+            IpNetworkParseError::InvalidAddress(ref s) =>
+                write!(f, "invalid ip address '{}'", s),
+            IpNetworkParseError::InvalidPrefix(ref s) =>
+                write!(f, "invalid prefix or netmask '{}'", s),
+            IpNetworkParseError::NonContiguousNetmask(ref s) =>
+                write!(f, "netmask '{}' is not a contiguous prefix", s),
+            IpNetworkParseError::PrefixTooLarge { prefix, max } =>
+                write!(f, "prefix /{} is too large (max /{})", prefix, max),
+        }
+    }
+}
+
+impl std::error::Error for IpNetworkParseError {}
+
+/// Counts the leading one-bits of `bits` (an address of `width` bits,
+/// right-aligned within the `u128`) and verifies that those are the only
+/// set bits, i.e. that the mask is a contiguous CIDR prefix.
+fn netmask_to_prefix(bits: u128, width: u32) -> Option<u8> {
+    let shifted = bits << (128 - width);
+    let ones = shifted.count_ones();
+    let expected = if ones == 0 { 0u128 } else { !0u128 << (128 - ones) };
+    if shifted == expected {
+        Some(ones as u8)
+    } else {
+        None
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = IpNetworkParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '/');
+        let addr_str = parts.next().unwrap_or("");
+        let prefix_str = parts.next().ok_or(IpNetworkParseError::MissingPrefix)?;
+
+        let addr: IpAddr = addr_str.parse()
+            .map_err(|_| IpNetworkParseError::InvalidAddress(addr_str.to_string()))?;
+        let max_prefix = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+
+        // accept a plain CIDR prefix length first, falling back to a
+        // dotted-quad (or IPv6) netmask.
+        let prefix = if let Ok(prefix) = prefix_str.parse::<u8>() {
+            prefix
+        } else {
+            let mask: IpAddr = prefix_str.parse()
+                .map_err(|_| IpNetworkParseError::InvalidPrefix(prefix_str.to_string()))?;
+            let (bits, width) = match mask {
+                IpAddr::V4(v4) => (u32::from(v4) as u128, 32),
+                IpAddr::V6(v6) => (u128::from(v6), 128),
+            };
+            netmask_to_prefix(bits, width)
+                .ok_or_else(|| IpNetworkParseError::NonContiguousNetmask(prefix_str.to_string()))?
+        };
+
+        if prefix > max_prefix {
+            return Err(IpNetworkParseError::PrefixTooLarge { prefix, max: max_prefix });
+        }
+
+        Ok(IpNetwork { addr, prefix })
+    }
+}
+
+/// `Scope=` values understood by systemd-networkd's `[Route]` section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    Global,
+    Link,
+    Host,
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match *self {
+            Scope::Global => "global",
+            Scope::Link => "link",
+            Scope::Host => "host",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct NetworkRoute {
     destination: IpNetwork,
     gateway: IpAddr,
+    metric: Option<u32>,
+    scope: Option<Scope>,
+    prefsrc: Option<IpAddr>,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// Errors produced when parsing a [`NetworkRoute`] from its `destination via
+/// gateway` textual form, e.g. `10.0.0.0/24 via 10.0.0.1`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkRouteParseError {
+    MissingGateway,
+    InvalidDestination(IpNetworkParseError),
+    InvalidGateway(String),
+}
+
+impl fmt::Display for NetworkRouteParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NetworkRouteParseError::MissingGateway =>
+                write!(f, "missing gateway (expected destination via gateway)"),
+            NetworkRouteParseError::InvalidDestination(ref e) =>
+                write!(f, "invalid route destination: {}", e),
+            NetworkRouteParseError::InvalidGateway(ref s) =>
+                write!(f, "invalid gateway address '{}'", s),
+        }
+    }
+}
+
+impl std::error::Error for NetworkRouteParseError {}
+
+impl FromStr for NetworkRoute {
+    type Err = NetworkRouteParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, " via ");
+        let destination_str = parts.next().unwrap_or("");
+        let gateway_str = parts.next().ok_or(NetworkRouteParseError::MissingGateway)?;
+
+        let destination = destination_str.parse()
+            .map_err(NetworkRouteParseError::InvalidDestination)?;
+        let gateway: IpAddr = gateway_str.parse()
+            .map_err(|_| NetworkRouteParseError::InvalidGateway(gateway_str.to_string()))?;
+
+        Ok(NetworkRoute { destination, gateway, metric: None, scope: None, prefsrc: None })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct MacAddr(pub u8, pub u8, pub u8,pub u8, pub u8, pub u8);
 
 impl fmt::Display for MacAddr {
@@ -48,6 +242,46 @@ impl fmt::Display for MacAddr {
     }
 }
 
+/// Errors produced when parsing a [`MacAddr`] from its colon-separated
+/// hex-octet textual form, e.g. `f4:00:34:09:73:ee`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MacAddrParseError {
+    WrongOctetCount(usize),
+    InvalidOctet(String),
+}
+
+impl fmt::Display for MacAddrParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MacAddrParseError::WrongOctetCount(n) =>
+                write!(f, "expected 6 colon-separated octets, found {}", n),
+            MacAddrParseError::InvalidOctet(ref s) =>
+                write!(f, "invalid hex octet '{}'", s),
+        }
+    }
+}
+
+impl std::error::Error for MacAddrParseError {}
+
+impl FromStr for MacAddr {
+    type Err = MacAddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let octets: Vec<&str> = s.split(':').collect();
+        if octets.len() != 6 {
+            return Err(MacAddrParseError::WrongOctetCount(octets.len()));
+        }
+
+        let mut parsed = [0u8; 6];
+        for (i, octet) in octets.iter().enumerate() {
+            parsed[i] = u8::from_str_radix(octet, 16)
+                .map_err(|_| MacAddrParseError::InvalidOctet(octet.to_string()))?;
+        }
+
+        Ok(MacAddr(parsed[0], parsed[1], parsed[2], parsed[3], parsed[4], parsed[5]))
+    }
+}
+
 /// for naming purposes an interface needs either a name or an address.
 /// it can have both. but it can't have neither.
 /// there isn't really a way to express this in the type system
@@ -63,6 +297,28 @@ pub struct Interface {
     ip_addresses: Vec<IpNetwork>,
     routes: Vec<NetworkRoute>,
     bond: Option<String>,
+    addressing: Addressing,
+    admin_state: Option<AdminState>,
+}
+
+/// How an interface's addresses are provisioned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Addressing {
+    /// Only the explicit `ip_addresses`/`routes` on the `Interface` are used.
+    Static,
+    Dhcp4,
+    Dhcp6,
+    DhcpDual,
+    LinkLocalOnly,
+}
+
+/// Administrative state of an interface, as in the standard Interfaces MIB
+/// (`ifAdminStatus`): whether it should be brought up at all, independent of
+/// its operational/link state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdminState {
+    Up,
+    Down,
 }
 
 #[derive(Clone, Debug)]
@@ -74,76 +330,509 @@ pub struct Section {
 #[derive(Clone, Debug)]
 pub struct Device {
     name: String,
-    kind: String,
+    kind: DeviceKind,
     mac_address: MacAddr,
     priority: Option<u32>,
     sections: Vec<Section>
 }
 
+/// The netdev types `Device` knows how to render a typed options section
+/// for automatically. `Other` covers anything else, leaving callers to
+/// build up `Device::sections` by hand as before.
+#[derive(Clone, Debug)]
+pub enum DeviceKind {
+    Bond(BondOptions),
+    Bridge(BridgeOptions),
+    Vlan(VlanOptions),
+    Other(String),
+}
+
+impl DeviceKind {
+    fn netdev_kind(&self) -> String {
+        match *self {
+            DeviceKind::Bond(_) => String::from("bond"),
+            DeviceKind::Bridge(_) => String::from("bridge"),
+            DeviceKind::Vlan(_) => String::from("vlan"),
+            DeviceKind::Other(ref kind) => kind.clone(),
+        }
+    }
+
+    /// The section generated from this kind's typed options, if any, using
+    /// systemd-networkd's `[Bond]`/`[VLAN]`/`[Bridge]` key names.
+    fn section(&self) -> Option<Section> {
+        match *self {
+            DeviceKind::Bond(ref opts) => Some(opts.build_section()),
+            DeviceKind::Bridge(ref opts) => Some(opts.build_section()),
+            DeviceKind::Vlan(ref opts) => Some(opts.build_section()),
+            DeviceKind::Other(_) => None,
+        }
+    }
+
+    /// The section generated from this kind's typed options, if any, using
+    /// NetworkManager's own `[bond]`/`[vlan]`/`[bridge]` key names. These are
+    /// not simply the lowercased networkd keys: `[bond]` in particular names
+    /// and units its keys differently (`miimon` is milliseconds, not
+    /// `MIIMonitorSec`'s duration-suffixed seconds).
+    fn nm_section(&self) -> Option<Section> {
+        match *self {
+            DeviceKind::Bond(ref opts) => Some(opts.build_nm_section()),
+            DeviceKind::Bridge(ref opts) => Some(opts.build_nm_section()),
+            DeviceKind::Vlan(ref opts) => Some(opts.build_nm_section()),
+            DeviceKind::Other(_) => None,
+        }
+    }
+}
+
+/// `Mode=` values understood by systemd-networkd's `[Bond]` section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BondMode {
+    BalanceRr,
+    ActiveBackup,
+    BalanceXor,
+    Broadcast,
+    Ieee8023ad,
+    BalanceTlb,
+    BalanceAlb,
+}
+
+impl fmt::Display for BondMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            BondMode::BalanceRr => "balance-rr",
+            BondMode::ActiveBackup => "active-backup",
+            BondMode::BalanceXor => "balance-xor",
+            BondMode::Broadcast => "broadcast",
+            BondMode::Ieee8023ad => "802.3ad",
+            BondMode::BalanceTlb => "balance-tlb",
+            BondMode::BalanceAlb => "balance-alb",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LacpTransmitRate {
+    Slow,
+    Fast,
+}
+
+impl fmt::Display for LacpTransmitRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match *self {
+            LacpTransmitRate::Slow => "slow",
+            LacpTransmitRate::Fast => "fast",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BondOptions {
+    pub mode: BondMode,
+    pub mii_monitor_sec: Option<u32>,
+    pub lacp_transmit_rate: Option<LacpTransmitRate>,
+}
+
+impl BondOptions {
+    fn build_section(&self) -> Section {
+        let mut attributes = vec![(String::from("Mode"), self.mode.to_string())];
+        if let Some(mii) = self.mii_monitor_sec {
+            attributes.push((String::from("MIIMonitorSec"), format!("{}s", mii)));
+        }
+        if let Some(rate) = self.lacp_transmit_rate {
+            attributes.push((String::from("LACPTransmitRate"), rate.to_string()));
+        }
+        Section { name: String::from("Bond"), attributes }
+    }
+
+    /// NetworkManager's `[bond]` keyfile section. `mode` and `lacp_rate`
+    /// take the same string values as the kernel bonding driver (and thus
+    /// the same values networkd uses), but `miimon` is a plain integer
+    /// number of milliseconds rather than a systemd duration string.
+    fn build_nm_section(&self) -> Section {
+        let mut attributes = vec![(String::from("mode"), self.mode.to_string())];
+        if let Some(mii) = self.mii_monitor_sec {
+            attributes.push((String::from("miimon"), (mii * 1000).to_string()));
+        }
+        if let Some(rate) = self.lacp_transmit_rate {
+            attributes.push((String::from("lacp_rate"), rate.to_string()));
+        }
+        Section { name: String::from("bond"), attributes }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VlanOptions {
+    pub id: u16,
+}
+
+impl VlanOptions {
+    fn build_section(&self) -> Section {
+        Section {
+            name: String::from("VLAN"),
+            attributes: vec![(String::from("Id"), self.id.to_string())],
+        }
+    }
+
+    /// NetworkManager's `[vlan]` keyfile section.
+    fn build_nm_section(&self) -> Section {
+        Section {
+            name: String::from("vlan"),
+            attributes: vec![(String::from("id"), self.id.to_string())],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BridgeOptions {
+    pub stp: Option<bool>,
+    pub priority: Option<u16>,
+}
+
+impl BridgeOptions {
+    fn build_section(&self) -> Section {
+        let mut attributes = vec![];
+        if let Some(stp) = self.stp {
+            attributes.push((String::from("STP"), stp.to_string()));
+        }
+        if let Some(priority) = self.priority {
+            attributes.push((String::from("Priority"), priority.to_string()));
+        }
+        Section { name: String::from("Bridge"), attributes }
+    }
+
+    /// NetworkManager's `[bridge]` keyfile section.
+    fn build_nm_section(&self) -> Section {
+        let mut attributes = vec![];
+        if let Some(stp) = self.stp {
+            attributes.push((String::from("stp"), stp.to_string()));
+        }
+        if let Some(priority) = self.priority {
+            attributes.push((String::from("priority"), priority.to_string()));
+        }
+        Section { name: String::from("bridge"), attributes }
+    }
+}
+
+/// Errors produced by [`Interface::validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterfaceValidationError {
+    DuplicateAddress(IpNetwork),
+    OverlappingRoutes(IpNetwork, IpNetwork),
+}
+
+impl fmt::Display for InterfaceValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InterfaceValidationError::DuplicateAddress(addr) =>
+                write!(f, "duplicate ip address {}", addr),
+            InterfaceValidationError::OverlappingRoutes(a, b) =>
+                write!(f, "overlapping route destinations {} and {}", a, b),
+        }
+    }
+}
+
+impl std::error::Error for InterfaceValidationError {}
+
 impl Interface {
-    pub fn unit_name(&self) -> String {
+    pub fn unit_name(&self, backend: &dyn NetworkBackend) -> String {
+        backend.interface_unit_name(self)
+    }
+    pub fn config(&self, backend: &dyn NetworkBackend) -> String {
+        backend.interface_config(self)
+    }
+
+    /// Checks for addresses assigned more than once and for routes whose
+    /// destinations overlap, either of which would otherwise be written out
+    /// as conflicting units. Routes that share a destination are only
+    /// flagged when they're exact duplicates of one another: distinct
+    /// routes to the same destination (e.g. a primary/backup default route
+    /// pair with different gateways or metrics) are a standard failover or
+    /// ECMP setup, not a conflict.
+    pub fn validate(&self) -> Result<(), InterfaceValidationError> {
+        for i in 0..self.ip_addresses.len() {
+            for j in (i + 1)..self.ip_addresses.len() {
+                if self.ip_addresses[i] == self.ip_addresses[j] {
+                    return Err(InterfaceValidationError::DuplicateAddress(self.ip_addresses[i]));
+                }
+            }
+        }
+
+        for i in 0..self.routes.len() {
+            for j in (i + 1)..self.routes.len() {
+                let (a, b) = (self.routes[i], self.routes[j]);
+                let conflicts = if a.destination == b.destination {
+                    a == b
+                } else {
+                    a.destination.overlaps(&b.destination)
+                };
+                if conflicts {
+                    return Err(InterfaceValidationError::OverlappingRoutes(a.destination, b.destination));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Device {
+    pub fn unit_name(&self, backend: &dyn NetworkBackend) -> String {
+        backend.device_unit_name(self)
+    }
+    pub fn config(&self, backend: &dyn NetworkBackend) -> String {
+        backend.device_config(self)
+    }
+}
+
+/// Renders `Interface`/`Device` data to the on-disk unit format understood
+/// by a particular init system. The same network model can be targeted at
+/// either systemd-networkd ([`NetworkdBackend`]) or NetworkManager
+/// ([`NetworkManagerBackend`]) by passing the desired backend to
+/// `Interface::config`/`Device::config`.
+pub trait NetworkBackend {
+    fn interface_unit_name(&self, interface: &Interface) -> String;
+    fn interface_config(&self, interface: &Interface) -> String;
+    fn device_unit_name(&self, device: &Device) -> String;
+    fn device_config(&self, device: &Device) -> String;
+}
+
+/// Renders units for systemd-networkd: `.network`/`.netdev` files made up
+/// of `[Match]`/`[Network]`/`[Address]`/`[Route]` and `[NetDev]` sections.
+pub struct NetworkdBackend;
+
+impl NetworkBackend for NetworkdBackend {
+    fn interface_unit_name(&self, interface: &Interface) -> String {
         format!("{:02}-{}.network",
-                self.priority.unwrap_or(10),
-                self.name.clone().unwrap_or_else(
+                interface.priority.unwrap_or(10),
+                interface.name.clone().unwrap_or_else(
                     // needs to be a lambda or we panic immediately
                     // yay, manual thunking!
-                    ||self.mac_address.unwrap_or_else(
+                    ||interface.mac_address.unwrap_or_else(
                         ||panic!("interface needs either name or mac address (or both)")
                     ).to_string()
                 ))
     }
-    pub fn config(&self) -> String {
+
+    fn interface_config(&self, interface: &Interface) -> String {
         let mut config = String::new();
 
         // [Match] section
         config.push_str("[Match]\n");
-        self.name.clone().map(|name| config.push_str(&format!("Name={}\n", name)));
-        self.mac_address.map(|mac| config.push_str(&format!("MACAddress={}\n", mac)));
+        interface.name.clone().map(|name| config.push_str(&format!("Name={}\n", name)));
+        interface.mac_address.map(|mac| config.push_str(&format!("MACAddress={}\n", mac)));
+
+        // [Link] section, when administratively down
+        if interface.admin_state == Some(AdminState::Down) {
+            config.push_str("\n[Link]\nUnmanaged=yes\nActivationPolicy=down\n");
+        }
 
         // [Network] section
         config.push_str("\n[Network]\n");
-        for ns in &self.nameservers {
+        match interface.addressing {
+            Addressing::Static => {}
+            Addressing::Dhcp4 => config.push_str("DHCP=ipv4\n"),
+            Addressing::Dhcp6 => config.push_str("DHCP=ipv6\n"),
+            Addressing::DhcpDual => config.push_str("DHCP=yes\n"),
+            Addressing::LinkLocalOnly => config.push_str("LinkLocalAddressing=yes\n"),
+        }
+        for ns in &interface.nameservers {
             config.push_str(&format!("DNS={}\n", ns))
         }
-        self.bond.clone().map(|bond| config.push_str(&format!("Bond={}\n", bond)));
+        interface.bond.clone().map(|bond| config.push_str(&format!("Bond={}\n", bond)));
 
         // [Address] sections
-        for addr in &self.ip_addresses {
+        for addr in &interface.ip_addresses {
             config.push_str(&format!("\n[Address]\nAddress={}\n", addr));
         }
 
         // [Route] sections
-        for route in &self.routes {
+        for route in &interface.routes {
             config.push_str(&format!("\n[Route]\nDestination={}\nGateway={}\n", route.destination, route.gateway));
+            if let Some(metric) = route.metric {
+                config.push_str(&format!("Metric={}\n", metric));
+            }
+            if let Some(scope) = route.scope {
+                config.push_str(&format!("Scope={}\n", scope));
+            }
+            if let Some(prefsrc) = route.prefsrc {
+                config.push_str(&format!("PreferredSource={}\n", prefsrc));
+            }
         }
 
         config
     }
-}
 
-impl Device {
-    pub fn unit_name(&self) -> String {
-        format!("{:02}-{}.netdev", self.priority.unwrap_or(10), self.name)
+    fn device_unit_name(&self, device: &Device) -> String {
+        format!("{:02}-{}.netdev", device.priority.unwrap_or(10), device.name)
     }
-    pub fn config(&self) -> String {
+
+    fn device_config(&self, device: &Device) -> String {
         let mut config = String::new();
 
         // [NetDev] section
         config.push_str("[NetDev]\n");
-        config.push_str(&format!("Name={}\n", self.name));
-        config.push_str(&format!("Kind={}\n", self.kind));
-        config.push_str(&format!("MACAddress={}\n", self.mac_address));
+        config.push_str(&format!("Name={}\n", device.name));
+        config.push_str(&format!("Kind={}\n", device.kind.netdev_kind()));
+        config.push_str(&format!("MACAddress={}\n", device.mac_address));
+
+        // section auto-generated from the typed netdev kind, if any
+        if let Some(section) = device.kind.section() {
+            push_section(&mut config, &section);
+        }
 
         // custom sections
-        for section in &self.sections {
-            config.push_str(&format!("\n[{}]\n", section.name));
-            for attr in &section.attributes {
-                config.push_str(&format!("{}={}\n", attr.0, attr.1));
+        for section in &device.sections {
+            push_section(&mut config, section);
+        }
+
+        config
+    }
+}
+
+/// Appends a `[section.name]` block followed by its `key=value` attributes.
+fn push_section(config: &mut String, section: &Section) {
+    config.push_str(&format!("\n[{}]\n", section.name));
+    for attr in &section.attributes {
+        config.push_str(&format!("{}={}\n", attr.0, attr.1));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AddressFamily {
+    V4,
+    V6,
+}
+
+impl AddressFamily {
+    fn matches(self, addr: &IpAddr) -> bool {
+        matches!((self, addr), (AddressFamily::V4, IpAddr::V4(_)) | (AddressFamily::V6, IpAddr::V6(_)))
+    }
+
+    fn section_name(self) -> &'static str {
+        match self {
+            AddressFamily::V4 => "ipv4",
+            AddressFamily::V6 => "ipv6",
+        }
+    }
+}
+
+/// Renders NetworkManager keyfiles (`.nmconnection`): INI-style files made
+/// up of `[connection]`, `[ethernet]`, and `[ipv4]`/`[ipv6]` sections.
+pub struct NetworkManagerBackend;
+
+impl NetworkManagerBackend {
+    fn connection_id(interface: &Interface) -> String {
+        interface.name.clone().unwrap_or_else(
+            ||interface.mac_address.unwrap_or_else(
+                ||panic!("interface needs either name or mac address (or both)")
+            ).to_string()
+        )
+    }
+}
+
+impl NetworkBackend for NetworkManagerBackend {
+    fn interface_unit_name(&self, interface: &Interface) -> String {
+        format!("{:02}-{}.nmconnection", interface.priority.unwrap_or(10), Self::connection_id(interface))
+    }
+
+    fn interface_config(&self, interface: &Interface) -> String {
+        let mut config = String::new();
+
+        // [connection] section
+        config.push_str("[connection]\n");
+        config.push_str(&format!("id={}\n", Self::connection_id(interface)));
+        config.push_str("type=ethernet\n");
+        if interface.admin_state == Some(AdminState::Down) {
+            config.push_str("autoconnect=false\n");
+        }
+
+        // [ethernet] section
+        if let Some(mac) = interface.mac_address {
+            config.push_str("\n[ethernet]\n");
+            config.push_str(&format!("mac-address={}\n", mac));
+        }
+
+        // [ipv4]/[ipv6] sections
+        for &family in &[AddressFamily::V4, AddressFamily::V6] {
+            let method = match (interface.addressing, family) {
+                (Addressing::Dhcp4, AddressFamily::V4) => "auto",
+                (Addressing::Dhcp6, AddressFamily::V6) => "auto",
+                (Addressing::DhcpDual, _) => "auto",
+                (Addressing::LinkLocalOnly, _) => "link-local",
+                _ => "manual",
+            };
+
+            let addresses: Vec<&IpNetwork> = interface.ip_addresses.iter()
+                .filter(|a| family.matches(&a.addr))
+                .collect();
+            let nameservers: Vec<&IpAddr> = interface.nameservers.iter()
+                .filter(|ns| family.matches(ns))
+                .collect();
+            let gateway = interface.routes.iter()
+                .find(|r| family.matches(&r.gateway) && r.destination.prefix == 0)
+                .map(|r| r.gateway);
+
+            if method == "manual" && addresses.is_empty() && nameservers.is_empty() {
+                continue;
+            }
+
+            config.push_str(&format!("\n[{}]\n", family.section_name()));
+            config.push_str(&format!("method={}\n", method));
+            if method == "manual" {
+                for (i, addr) in addresses.iter().enumerate() {
+                    match gateway {
+                        Some(gw) if i == 0 => config.push_str(&format!("address{}={},{}\n", i + 1, addr, gw)),
+                        _ => config.push_str(&format!("address{}={}\n", i + 1, addr)),
+                    }
+                }
+            }
+            if !nameservers.is_empty() {
+                let dns = nameservers.iter().map(|ns| ns.to_string()).collect::<Vec<_>>().join(";");
+                config.push_str(&format!("dns={};\n", dns));
             }
         }
 
         config
     }
+
+    fn device_unit_name(&self, device: &Device) -> String {
+        format!("{:02}-{}.nmconnection", device.priority.unwrap_or(10), device.name)
+    }
+
+    fn device_config(&self, device: &Device) -> String {
+        let mut config = String::new();
+
+        // [connection] section
+        config.push_str("[connection]\n");
+        config.push_str(&format!("id={}\n", device.name));
+        config.push_str(&format!("type={}\n", device.kind.netdev_kind()));
+
+        // [ethernet] section
+        config.push_str("\n[ethernet]\n");
+        config.push_str(&format!("mac-address={}\n", device.mac_address));
+
+        // section auto-generated from the typed netdev kind, if any, using
+        // NetworkManager's own key names rather than networkd's
+        if let Some(section) = device.kind.nm_section() {
+            push_section(&mut config, &section);
+        }
+
+        // custom sections
+        for section in &device.sections {
+            push_lowercase_section(&mut config, section);
+        }
+
+        config
+    }
+}
+
+/// Appends a `[section.name]` block, lowercasing the section name and its
+/// attribute keys to match NetworkManager keyfile conventions.
+fn push_lowercase_section(config: &mut String, section: &Section) {
+    config.push_str(&format!("\n[{}]\n", section.name.to_lowercase()));
+    for attr in &section.attributes {
+        config.push_str(&format!("{}={}\n", attr.0.to_lowercase(), attr.1));
+    }
 }
 
 #[cfg(test)]
@@ -183,6 +872,108 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mac_addr_from_str() {
+        assert_eq!("f4:00:34:09:73:ee".parse::<MacAddr>().unwrap().to_string(), "f4:00:34:09:73:ee");
+        assert_eq!("00:00:00:00:00:00".parse::<MacAddr>().unwrap().to_string(), "00:00:00:00:00:00");
+
+        assert_eq!("f4:00:34:09:73".parse::<MacAddr>(), Err(MacAddrParseError::WrongOctetCount(5)));
+        assert_eq!("f4:00:34:09:73:ee:00".parse::<MacAddr>(), Err(MacAddrParseError::WrongOctetCount(7)));
+        assert_eq!("zz:00:34:09:73:ee".parse::<MacAddr>(), Err(MacAddrParseError::InvalidOctet(String::from("zz"))));
+    }
+
+    #[test]
+    fn ip_network_from_str_cidr() {
+        let n: IpNetwork = "10.0.0.1/24".parse().unwrap();
+        assert_eq!(n.to_string(), "10.0.0.1/24");
+
+        let n: IpNetwork = "fe80::1/64".parse().unwrap();
+        assert_eq!(n.to_string(), "fe80::1/64");
+    }
+
+    #[test]
+    fn ip_network_from_str_netmask() {
+        let n: IpNetwork = "10.0.0.1/255.255.255.0".parse().unwrap();
+        assert_eq!(n.to_string(), "10.0.0.1/24");
+
+        let n: IpNetwork = "10.0.0.1/255.255.255.255".parse().unwrap();
+        assert_eq!(n.to_string(), "10.0.0.1/32");
+
+        let n: IpNetwork = "10.0.0.1/0.0.0.0".parse().unwrap();
+        assert_eq!(n.to_string(), "10.0.0.1/0");
+    }
+
+    #[test]
+    fn ip_network_from_str_errors() {
+        assert_eq!("10.0.0.1".parse::<IpNetwork>(), Err(IpNetworkParseError::MissingPrefix));
+        assert_eq!("nope/24".parse::<IpNetwork>(), Err(IpNetworkParseError::InvalidAddress(String::from("nope"))));
+        assert_eq!("10.0.0.1/nope".parse::<IpNetwork>(), Err(IpNetworkParseError::InvalidPrefix(String::from("nope"))));
+        assert_eq!(
+            "10.0.0.1/255.255.0.255".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::NonContiguousNetmask(String::from("255.255.0.255")))
+        );
+        assert_eq!(
+            "10.0.0.1/33".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::PrefixTooLarge { prefix: 33, max: 32 })
+        );
+        assert_eq!(
+            "::1/129".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::PrefixTooLarge { prefix: 129, max: 128 })
+        );
+    }
+
+    #[test]
+    fn network_route_from_str() {
+        let r: NetworkRoute = "10.0.0.0/24 via 10.0.0.1".parse().unwrap();
+        assert_eq!(r.destination.to_string(), "10.0.0.0/24");
+        assert_eq!(r.gateway.to_string(), "10.0.0.1");
+
+        assert_eq!("10.0.0.0/24".parse::<NetworkRoute>(), Err(NetworkRouteParseError::MissingGateway));
+        assert_eq!(
+            "10.0.0.0/24 via nope".parse::<NetworkRoute>(),
+            Err(NetworkRouteParseError::InvalidGateway(String::from("nope")))
+        );
+        assert!(matches!(
+            "nope/24 via 10.0.0.1".parse::<NetworkRoute>(),
+            Err(NetworkRouteParseError::InvalidDestination(_))
+        ));
+    }
+
+    #[test]
+    fn ip_network_network() {
+        let n: IpNetwork = "10.0.0.130/24".parse().unwrap();
+        assert_eq!(n.network(), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)));
+
+        let n: IpNetwork = "10.0.0.1/32".parse().unwrap();
+        assert_eq!(n.network(), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+
+        let n: IpNetwork = "10.0.0.1/0".parse().unwrap();
+        assert_eq!(n.network(), IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)));
+
+        let n: IpNetwork = "fe80::f696:34ff:fe09:7347/64".parse().unwrap();
+        assert_eq!(n.network(), IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn ip_network_contains() {
+        let n: IpNetwork = "10.0.0.0/24".parse().unwrap();
+        assert!(n.contains(&IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))));
+        assert!(n.contains(&IpAddr::V4(Ipv4Addr::new(10, 0, 0, 255))));
+        assert!(!n.contains(&IpAddr::V4(Ipv4Addr::new(10, 0, 1, 0))));
+        assert!(!n.contains(&IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1))));
+    }
+
+    #[test]
+    fn ip_network_overlaps() {
+        let a: IpNetwork = "10.0.0.0/24".parse().unwrap();
+        let b: IpNetwork = "10.0.0.128/25".parse().unwrap();
+        let c: IpNetwork = "10.0.1.0/24".parse().unwrap();
+
+        assert!(a.overlaps(&b));
+        assert!(b.overlaps(&a));
+        assert!(!a.overlaps(&c));
+    }
+
     #[test]
     fn interface_unit_name() {
         let is = vec![
@@ -194,6 +985,8 @@ mod tests {
                 ip_addresses: vec![],
                 routes: vec![],
                 bond: None,
+                addressing: Addressing::Static,
+                admin_state: None,
             }, "20-lo.network"),
             (Interface {
                 name: Some(String::from("lo")),
@@ -203,6 +996,8 @@ mod tests {
                 ip_addresses: vec![],
                 routes: vec![],
                 bond: None,
+                addressing: Addressing::Static,
+                admin_state: None,
             }, "10-lo.network"),
             (Interface {
                 name: None,
@@ -212,6 +1007,8 @@ mod tests {
                 ip_addresses: vec![],
                 routes: vec![],
                 bond: None,
+                addressing: Addressing::Static,
+                admin_state: None,
             }, "20-00:00:00:00:00:00.network"),
             (Interface {
                 name: Some(String::from("lo")),
@@ -221,11 +1018,13 @@ mod tests {
                 ip_addresses: vec![],
                 routes: vec![],
                 bond: None,
+                addressing: Addressing::Static,
+                admin_state: None,
             }, "20-lo.network"),
         ];
 
         for (i, s) in is {
-            assert_eq!(i.unit_name(), s);
+            assert_eq!(i.unit_name(&NetworkdBackend), s);
         }
     }
 
@@ -240,8 +1039,10 @@ mod tests {
             ip_addresses: vec![],
             routes: vec![],
             bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
         };
-        let _name = i.unit_name();
+        let _name = i.unit_name(&NetworkdBackend);
     }
 
     #[test]
@@ -249,14 +1050,14 @@ mod tests {
         let ds = vec![
             (Device {
                 name: String::from("vlan0"),
-                kind: String::from("vlan"),
+                kind: DeviceKind::Other(String::from("vlan")),
                 mac_address: MacAddr(0,0,0,0,0,0),
                 priority: Some(20),
                 sections: vec![],
             }, "20-vlan0.netdev"),
             (Device {
                 name: String::from("vlan0"),
-                kind: String::from("vlan"),
+                kind: DeviceKind::Other(String::from("vlan")),
                 mac_address: MacAddr(0,0,0,0,0,0),
                 priority: None,
                 sections: vec![],
@@ -264,7 +1065,7 @@ mod tests {
         ];
 
         for (d, s) in ds {
-            assert_eq!(d.unit_name(), s);
+            assert_eq!(d.unit_name(&NetworkdBackend), s);
         }
     }
 
@@ -296,9 +1097,14 @@ mod tests {
                             prefix: 8,
                         },
                         gateway: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+                        metric: Some(100),
+                        scope: Some(Scope::Link),
+                        prefsrc: Some(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2))),
                     }
                 ],
                 bond: Some(String::from("james")),
+                addressing: Addressing::Static,
+                admin_state: None,
             }, "[Match]
 Name=lo
 MACAddress=00:00:00:00:00:00
@@ -317,6 +1123,9 @@ Address=::1/128
 [Route]
 Destination=127.0.0.1/8
 Gateway=127.0.0.1
+Metric=100
+Scope=link
+PreferredSource=127.0.0.2
 "),
             // this isn't really a valid interface object, but it's testing
             // the minimum possible configuration for all peices at the same
@@ -329,6 +1138,8 @@ Gateway=127.0.0.1
                 ip_addresses: vec![],
                 routes: vec![],
                 bond: None,
+                addressing: Addressing::Static,
+                admin_state: None,
             }, "[Match]
 
 [Network]
@@ -336,16 +1147,233 @@ Gateway=127.0.0.1
         ];
 
         for (i, s) in is {
-            assert_eq!(i.config(), s);
+            assert_eq!(i.config(&NetworkdBackend), s);
         }
     }
 
+    #[test]
+    fn interface_validate_duplicate_address() {
+        let addr = IpNetwork { addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), prefix: 24 };
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![addr, addr],
+            routes: vec![],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
+        };
+        assert_eq!(i.validate(), Err(InterfaceValidationError::DuplicateAddress(addr)));
+    }
+
+    #[test]
+    fn interface_validate_overlapping_routes() {
+        let a = IpNetwork { addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), prefix: 24 };
+        let b = IpNetwork { addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 128)), prefix: 25 };
+        let gateway = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 254));
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![
+                NetworkRoute { destination: a, gateway, metric: None, scope: None, prefsrc: None },
+                NetworkRoute { destination: b, gateway, metric: None, scope: None, prefsrc: None },
+            ],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
+        };
+        assert_eq!(i.validate(), Err(InterfaceValidationError::OverlappingRoutes(a, b)));
+    }
+
+    #[test]
+    fn interface_validate_duplicate_route() {
+        let destination = IpNetwork { addr: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), prefix: 0 };
+        let gateway = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 254));
+        let route = NetworkRoute { destination, gateway, metric: None, scope: None, prefsrc: None };
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![route, route],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
+        };
+        assert_eq!(i.validate(), Err(InterfaceValidationError::OverlappingRoutes(destination, destination)));
+    }
+
+    #[test]
+    fn interface_validate_allows_multiple_gateways_to_same_destination() {
+        let destination = IpNetwork { addr: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), prefix: 0 };
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![
+                NetworkRoute {
+                    destination,
+                    gateway: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                    metric: Some(100),
+                    scope: None,
+                    prefsrc: None,
+                },
+                NetworkRoute {
+                    destination,
+                    gateway: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)),
+                    metric: Some(200),
+                    scope: None,
+                    prefsrc: None,
+                },
+            ],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
+        };
+        assert_eq!(i.validate(), Ok(()));
+    }
+
+    #[test]
+    fn interface_validate_ok() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![
+                IpNetwork { addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), prefix: 24 },
+                IpNetwork { addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), prefix: 24 },
+            ],
+            routes: vec![],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
+        };
+        assert_eq!(i.validate(), Ok(()));
+    }
+
+    #[test]
+    fn interface_config_dhcp_dual() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![],
+            bond: None,
+            addressing: Addressing::DhcpDual,
+            admin_state: None,
+        };
+        assert_eq!(i.config(&NetworkdBackend), "[Match]
+Name=eth0
+
+[Network]
+DHCP=yes
+");
+    }
+
+    #[test]
+    fn interface_config_link_local_only() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![],
+            bond: None,
+            addressing: Addressing::LinkLocalOnly,
+            admin_state: None,
+        };
+        assert_eq!(i.config(&NetworkdBackend), "[Match]
+Name=eth0
+
+[Network]
+LinkLocalAddressing=yes
+");
+    }
+
+    #[test]
+    fn interface_config_admin_down() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: Some(AdminState::Down),
+        };
+        assert_eq!(i.config(&NetworkdBackend), "[Match]
+Name=eth0
+
+[Link]
+Unmanaged=yes
+ActivationPolicy=down
+
+[Network]
+");
+    }
+
+    #[test]
+    fn network_manager_interface_config_dhcp4() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![],
+            bond: None,
+            addressing: Addressing::Dhcp4,
+            admin_state: None,
+        };
+        assert_eq!(i.config(&NetworkManagerBackend), "[connection]
+id=eth0
+type=ethernet
+
+[ipv4]
+method=auto
+");
+    }
+
+    #[test]
+    fn network_manager_interface_config_admin_down() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: Some(AdminState::Down),
+        };
+        assert_eq!(i.config(&NetworkManagerBackend), "[connection]
+id=eth0
+type=ethernet
+autoconnect=false
+");
+    }
+
     #[test]
     fn device_config() {
         let ds = vec![
             (Device {
                 name: String::from("vlan0"),
-                kind: String::from("vlan"),
+                kind: DeviceKind::Other(String::from("vlan")),
                 mac_address: MacAddr(0,0,0,0,0,0),
                 priority: Some(20),
                 sections: vec![
@@ -374,7 +1402,7 @@ oingo=boingo
 "),
             (Device {
                 name: String::from("vlan0"),
-                kind: String::from("vlan"),
+                kind: DeviceKind::Other(String::from("vlan")),
                 mac_address: MacAddr(0,0,0,0,0,0),
                 priority: Some(20),
                 sections: vec![],
@@ -386,7 +1414,259 @@ MACAddress=00:00:00:00:00:00
         ];
 
         for (d, s) in ds {
-            assert_eq!(d.config(), s);
+            assert_eq!(d.config(&NetworkdBackend), s);
         }
     }
+
+    #[test]
+    fn bond_device_config() {
+        let d = Device {
+            name: String::from("bond0"),
+            kind: DeviceKind::Bond(BondOptions {
+                mode: BondMode::Ieee8023ad,
+                mii_monitor_sec: Some(1),
+                lacp_transmit_rate: Some(LacpTransmitRate::Fast),
+            }),
+            mac_address: MacAddr(0,0,0,0,0,0),
+            priority: Some(20),
+            sections: vec![],
+        };
+
+        assert_eq!(d.config(&NetworkdBackend), "[NetDev]
+Name=bond0
+Kind=bond
+MACAddress=00:00:00:00:00:00
+
+[Bond]
+Mode=802.3ad
+MIIMonitorSec=1s
+LACPTransmitRate=fast
+");
+    }
+
+    #[test]
+    fn vlan_device_config() {
+        let d = Device {
+            name: String::from("vlan0"),
+            kind: DeviceKind::Vlan(VlanOptions { id: 100 }),
+            mac_address: MacAddr(0,0,0,0,0,0),
+            priority: Some(20),
+            sections: vec![],
+        };
+
+        assert_eq!(d.config(&NetworkdBackend), "[NetDev]
+Name=vlan0
+Kind=vlan
+MACAddress=00:00:00:00:00:00
+
+[VLAN]
+Id=100
+");
+    }
+
+    #[test]
+    fn bridge_device_config() {
+        let d = Device {
+            name: String::from("br0"),
+            kind: DeviceKind::Bridge(BridgeOptions { stp: Some(true), priority: Some(32768) }),
+            mac_address: MacAddr(0,0,0,0,0,0),
+            priority: Some(20),
+            sections: vec![],
+        };
+
+        assert_eq!(d.config(&NetworkdBackend), "[NetDev]
+Name=br0
+Kind=bridge
+MACAddress=00:00:00:00:00:00
+
+[Bridge]
+STP=true
+Priority=32768
+");
+    }
+
+    #[test]
+    fn network_manager_bond_device_config() {
+        let d = Device {
+            name: String::from("bond0"),
+            kind: DeviceKind::Bond(BondOptions {
+                mode: BondMode::Ieee8023ad,
+                mii_monitor_sec: Some(1),
+                lacp_transmit_rate: Some(LacpTransmitRate::Fast),
+            }),
+            mac_address: MacAddr(0,0,0,0,0,0),
+            priority: Some(20),
+            sections: vec![],
+        };
+
+        assert_eq!(d.config(&NetworkManagerBackend), "[connection]
+id=bond0
+type=bond
+
+[ethernet]
+mac-address=00:00:00:00:00:00
+
+[bond]
+mode=802.3ad
+miimon=1000
+lacp_rate=fast
+");
+    }
+
+    #[test]
+    fn network_manager_vlan_device_config() {
+        let d = Device {
+            name: String::from("vlan0"),
+            kind: DeviceKind::Vlan(VlanOptions { id: 100 }),
+            mac_address: MacAddr(0,0,0,0,0,0),
+            priority: Some(20),
+            sections: vec![],
+        };
+
+        assert_eq!(d.config(&NetworkManagerBackend), "[connection]
+id=vlan0
+type=vlan
+
+[ethernet]
+mac-address=00:00:00:00:00:00
+
+[vlan]
+id=100
+");
+    }
+
+    #[test]
+    fn network_manager_bridge_device_config() {
+        let d = Device {
+            name: String::from("br0"),
+            kind: DeviceKind::Bridge(BridgeOptions { stp: Some(true), priority: Some(32768) }),
+            mac_address: MacAddr(0,0,0,0,0,0),
+            priority: Some(20),
+            sections: vec![],
+        };
+
+        assert_eq!(d.config(&NetworkManagerBackend), "[connection]
+id=br0
+type=bridge
+
+[ethernet]
+mac-address=00:00:00:00:00:00
+
+[bridge]
+stp=true
+priority=32768
+");
+    }
+
+    #[test]
+    fn network_manager_interface_unit_name() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: Some(MacAddr(0,0,0,0,0,0)),
+            priority: Some(20),
+            nameservers: vec![],
+            ip_addresses: vec![],
+            routes: vec![],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
+        };
+        assert_eq!(i.unit_name(&NetworkManagerBackend), "20-eth0.nmconnection");
+    }
+
+    #[test]
+    fn network_manager_interface_config() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: Some(MacAddr(0xf4,0x00,0x34,0x09,0x73,0xee)),
+            priority: Some(20),
+            nameservers: vec![
+                IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)),
+            ],
+            ip_addresses: vec![
+                IpNetwork {
+                    addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                    prefix: 24,
+                },
+            ],
+            routes: vec![
+                NetworkRoute {
+                    destination: IpNetwork {
+                        addr: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
+                        prefix: 0,
+                    },
+                    gateway: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 254)),
+                    metric: None,
+                    scope: None,
+                    prefsrc: None,
+                },
+            ],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
+        };
+
+        assert_eq!(i.config(&NetworkManagerBackend), "[connection]
+id=eth0
+type=ethernet
+
+[ethernet]
+mac-address=f4:00:34:09:73:ee
+
+[ipv4]
+method=manual
+address1=10.0.0.1/24,10.0.0.254
+dns=8.8.8.8;
+");
+    }
+
+    #[test]
+    fn network_manager_interface_config_picks_default_route_gateway() {
+        let i = Interface {
+            name: Some(String::from("eth0")),
+            mac_address: None,
+            priority: None,
+            nameservers: vec![],
+            ip_addresses: vec![
+                IpNetwork {
+                    addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                    prefix: 24,
+                },
+            ],
+            routes: vec![
+                NetworkRoute {
+                    destination: IpNetwork {
+                        addr: IpAddr::V4(Ipv4Addr::new(192, 168, 0, 0)),
+                        prefix: 16,
+                    },
+                    gateway: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                    metric: None,
+                    scope: None,
+                    prefsrc: None,
+                },
+                NetworkRoute {
+                    destination: IpNetwork {
+                        addr: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
+                        prefix: 0,
+                    },
+                    gateway: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 254)),
+                    metric: None,
+                    scope: None,
+                    prefsrc: None,
+                },
+            ],
+            bond: None,
+            addressing: Addressing::Static,
+            admin_state: None,
+        };
+
+        assert_eq!(i.config(&NetworkManagerBackend), "[connection]
+id=eth0
+type=ethernet
+
+[ipv4]
+method=manual
+address1=10.0.0.1/24,10.0.0.254
+");
+    }
 }
\ No newline at end of file